@@ -168,9 +168,14 @@ fn verify_unlock(
         .any(|txin| txin.previous_output == expected_out_point);
     ensure!(is_found, Error::UtxoSealMismatch);
 
-    // check bitcoin transaction exists in light client
+    // check bitcoin transaction exists in light client, buried under at
+    // least `config.min_confirmations()` blocks
+    //
+    // `btc_tx_proof` is a BIP37 partial merkle tree, so `btc_tx` can sit
+    // at any position in its block; the matched index is derived from
+    // the proof itself rather than passed in.
     let btc_tx_proof = unlock_witness.btc_tx_proof().raw_data();
-    check_btc_tx_exists(&config.btc_lc_type_hash(), &btc_tx.txid, 0, &btc_tx_proof)?;
+    check_btc_tx_exists(config, &btc_tx.txid, &btc_tx_proof)?;
 
     // verify commitment
     check_btc_tx_commitment(config, btc_tx, ckb_tx, unlock_witness)?;
@@ -188,22 +193,27 @@ fn check_btc_tx_commitment(
     let btc_commitment = bitcoin::extract_commitment(btc_tx).ok_or(Error::BadBtcCommitment)?;
 
     // 2. verify commitment extra data
+    //
+    // version 0 packs input/output counts as a single byte each (up to
+    // 255 committed cells); version 1 packs them as little-endian u16
+    // (up to 65535), for batch transfers that outgrow version 0.
     let raw_ckb_tx = ckb_tx.raw();
     let version: u16 = unlock_witness.version().unpack();
-    let input_len: u8 = unlock_witness.extra_data().input_len().into();
-    let output_len: u8 = unlock_witness.extra_data().output_len().into();
-    ensure_eq!(version, 0, Error::UnknownCommitmentVersion);
+    let extra_data = unlock_witness.extra_data();
+    let (input_len, output_len) = decode_commitment_lengths(version, &extra_data)?;
+    let input_len = input_len as usize;
+    let output_len = output_len as usize;
     ensure!(input_len > 0, Error::BadBtcCommitment);
     ensure!(output_len > 0, Error::BadBtcCommitment);
     let inputs_are_committed = QueryIter::new(load_cell_type_hash, Source::Input)
-        .skip(input_len.into())
+        .skip(input_len)
         .all(|type_hash| type_hash.is_none());
     ensure!(inputs_are_committed, Error::CommitmentMismatch);
 
     let outputs_are_committed = raw_ckb_tx
         .outputs()
         .iter()
-        .skip(output_len.into())
+        .skip(output_len)
         .all(|output| output.type_().is_none());
     ensure!(outputs_are_committed, Error::CommitmentMismatch);
 
@@ -211,15 +221,22 @@ fn check_btc_tx_commitment(
     let mut hasher = Sha256::new();
     hasher.update(b"RGB++");
     hasher.update(version.to_le_bytes());
-    hasher.update([input_len, output_len]);
-    for input in raw_ckb_tx.inputs().iter().take(input_len.into()) {
+    match version {
+        0 => hasher.update([input_len as u8, output_len as u8]),
+        1 => {
+            hasher.update((input_len as u16).to_le_bytes());
+            hasher.update((output_len as u16).to_le_bytes());
+        }
+        _ => unreachable!("version already validated above"),
+    }
+    for input in raw_ckb_tx.inputs().iter().take(input_len) {
         hasher.update(input.previous_output().as_slice());
     }
     for (output, data) in raw_ckb_tx
         .outputs()
         .iter()
         .zip(raw_ckb_tx.outputs_data().iter())
-        .take(output_len.into())
+        .take(output_len)
     {
         let lock = output.lock().to_entity();
         if is_btc_time_lock(config, &lock) {
@@ -0,0 +1,327 @@
+//! Minimal Bitcoin transaction parsing.
+//!
+//! Input scripts and witnesses are skipped. What is kept is the version,
+//! the lock time, the txid, the `previous_output` and `sequence` of every
+//! input (to match against a cell's seal) and the outputs (to find the
+//! OP_RETURN commitment and to re-derive BTC-time-lock seals).
+//! Both legacy and SegWit (BIP144) serializations are accepted, since
+//! most real RGB++ commitment carriers are SegWit/Taproot spends.
+
+use alloc::vec::Vec;
+use ckb_std::ckb_types::packed::Byte32;
+use ckb_std::ckb_types::prelude::*;
+
+pub mod merkle;
+
+pub use sha2::{Digest, Sha256};
+
+use crate::error::Error;
+
+/// Minimum number of BTC blocks a `BTCTimeLock` cell must wait for after
+/// its sealing transaction is buried, before it can be spent.
+pub const MIN_BTC_TIME_LOCK_AFTER: u32 = 6;
+
+/// Single round of SHA-256.
+pub fn sha2(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// Bitcoin's double SHA-256 (used for txids and merkle nodes).
+pub fn dsha256(data: &[u8]) -> [u8; 32] {
+    sha2(&sha2(data))
+}
+
+pub struct TxIn {
+    /// `(txid, vout)` of the output this input spends.
+    pub previous_output: (Byte32, u32),
+    pub sequence: u32,
+}
+
+pub struct TxOut {
+    pub value: u64,
+    pub script_pubkey: Vec<u8>,
+}
+
+pub struct BTCTx {
+    pub version: i32,
+    pub inputs: Vec<TxIn>,
+    pub outputs: Vec<TxOut>,
+    pub lock_time: u32,
+    /// Double-SHA256 of the non-witness serialization.
+    pub txid: Byte32,
+}
+
+struct Cursor<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let end = self.pos.checked_add(len).ok_or(Error::BadBtcTx)?;
+        let bytes = self.data.get(self.pos..end).ok_or(Error::BadBtcTx)?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn take_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn take_u32_le(&mut self) -> Result<u32, Error> {
+        let bytes: [u8; 4] = self.take(4)?.try_into().map_err(|_| Error::BadBtcTx)?;
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    fn take_u64_le(&mut self) -> Result<u64, Error> {
+        let bytes: [u8; 8] = self.take(8)?.try_into().map_err(|_| Error::BadBtcTx)?;
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    /// Bitcoin's CompactSize / varint encoding.
+    fn take_varint(&mut self) -> Result<u64, Error> {
+        let prefix = self.take_u8()?;
+        match prefix {
+            0xfd => {
+                let bytes: [u8; 2] = self.take(2)?.try_into().map_err(|_| Error::BadBtcTx)?;
+                Ok(u16::from_le_bytes(bytes) as u64)
+            }
+            0xfe => {
+                let bytes: [u8; 4] = self.take(4)?.try_into().map_err(|_| Error::BadBtcTx)?;
+                Ok(u32::from_le_bytes(bytes) as u64)
+            }
+            0xff => {
+                let bytes: [u8; 8] = self.take(8)?.try_into().map_err(|_| Error::BadBtcTx)?;
+                Ok(u64::from_le_bytes(bytes))
+            }
+            _ => Ok(prefix as u64),
+        }
+    }
+}
+
+/// SegWit marker byte, immediately following the 4-byte version when the
+/// transaction carries witness data (BIP144).
+const SEGWIT_MARKER: u8 = 0x00;
+
+/// Parse a Bitcoin transaction, legacy or SegWit-serialized (BIP144).
+///
+/// The returned `txid` is always the double-SHA256 of the non-witness
+/// serialization (version ‖ inputs ‖ outputs ‖ locktime): marker, flag
+/// and witness data are parsed far enough to advance past them, but
+/// never folded into the hash.
+///
+/// Known limitation: a legacy tx's first varint (input count) and the
+/// SegWit marker are both `0x00`, disambiguated here by requiring the
+/// following byte (a real flag is never zero) to also be non-zero. This
+/// is unresolved BIP144 ambiguity, not a deliberately-chosen tradeoff: a
+/// zero-input legacy tx whose *output* count also happens to be non-zero
+/// byte-collides with a real marker+flag pair and is still misparsed as
+/// SegWit (see `zero_input_legacy_tx_with_nonzero_output_count_is_misparsed`
+/// below). RGB++ commitment txs always have at least one input (the
+/// sealed UTXO, checked by `verify_unlock`'s `is_found` match before
+/// this tx is ever examined further), so this can't be hit on any path
+/// that matters to this contract.
+pub fn parse_btc_tx(raw: &[u8]) -> Result<BTCTx, Error> {
+    let mut cursor = Cursor::new(raw);
+    let version = cursor.take_u32_le()? as i32;
+
+    let is_segwit = cursor.data.get(cursor.pos) == Some(&SEGWIT_MARKER)
+        && matches!(cursor.data.get(cursor.pos + 1), Some(&flag) if flag != 0);
+    if is_segwit {
+        cursor.take(2)?; // marker + flag
+    }
+
+    let io_start = cursor.pos;
+
+    let input_count = cursor.take_varint()?;
+    let mut inputs = Vec::with_capacity(input_count as usize);
+    for _ in 0..input_count {
+        let txid = Byte32::from_slice(cursor.take(32)?).map_err(|_| Error::BadBtcTx)?;
+        let vout = cursor.take_u32_le()?;
+        let script_len = cursor.take_varint()?;
+        cursor.take(script_len as usize)?;
+        let sequence = cursor.take_u32_le()?;
+        inputs.push(TxIn {
+            previous_output: (txid, vout),
+            sequence,
+        });
+    }
+
+    let output_count = cursor.take_varint()?;
+    let mut outputs = Vec::with_capacity(output_count as usize);
+    for _ in 0..output_count {
+        let value = cursor.take_u64_le()?;
+        let script_len = cursor.take_varint()?;
+        let script_pubkey = cursor.take(script_len as usize)?.to_vec();
+        outputs.push(TxOut {
+            value,
+            script_pubkey,
+        });
+    }
+
+    let io_end = cursor.pos;
+
+    if is_segwit {
+        // One witness stack per input: a varint item count, then each
+        // item as a varint-length-prefixed byte string.
+        for _ in 0..input_count {
+            let item_count = cursor.take_varint()?;
+            for _ in 0..item_count {
+                let item_len = cursor.take_varint()?;
+                cursor.take(item_len as usize)?;
+            }
+        }
+    }
+
+    let lock_time_start = cursor.pos;
+    let lock_time = cursor.take_u32_le()?;
+
+    let mut preimage = Vec::with_capacity(4 + (io_end - io_start) + 4);
+    preimage.extend_from_slice(&raw[..4]);
+    preimage.extend_from_slice(&raw[io_start..io_end]);
+    preimage.extend_from_slice(&raw[lock_time_start..cursor.pos]);
+    let txid = Byte32::from_slice(&dsha256(&preimage)).map_err(|_| Error::BadBtcTx)?;
+
+    Ok(BTCTx {
+        version,
+        inputs,
+        outputs,
+        lock_time,
+        txid,
+    })
+}
+
+const OP_RETURN: u8 = 0x6a;
+const OP_PUSHDATA1: u8 = 0x4c;
+
+/// Find the RGB++ commitment carried in an `OP_RETURN` output.
+///
+/// RGB++ txs put the commitment (a 32-byte double-SHA256 digest) as the
+/// pushed data of the first `OP_RETURN` output.
+pub fn extract_commitment(btc_tx: &BTCTx) -> Option<Byte32> {
+    for output in &btc_tx.outputs {
+        let script = &output.script_pubkey;
+        if script.first() != Some(&OP_RETURN) {
+            continue;
+        }
+        let (len, data_start) = match script.get(1) {
+            Some(&len) if len < OP_PUSHDATA1 => (len as usize, 2),
+            Some(&OP_PUSHDATA1) => (*script.get(2)? as usize, 3),
+            _ => continue,
+        };
+        if len != 32 {
+            continue;
+        }
+        let data = script.get(data_start..data_start + len)?;
+        return Byte32::from_slice(data).ok();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One input (spending a zeroed outpoint with an empty scriptSig)
+    /// and one output (zero value, empty scriptPubKey), legacy-encoded.
+    fn legacy_tx() -> Vec<u8> {
+        let mut raw = Vec::new();
+        raw.extend_from_slice(&1i32.to_le_bytes()); // version
+        raw.push(1); // input count
+        raw.extend_from_slice(&[0u8; 32]); // prevout txid
+        raw.extend_from_slice(&0u32.to_le_bytes()); // prevout vout
+        raw.push(0); // empty scriptSig
+        raw.extend_from_slice(&0xffff_ffffu32.to_le_bytes()); // sequence
+        raw.push(1); // output count
+        raw.extend_from_slice(&0u64.to_le_bytes()); // value
+        raw.push(0); // empty scriptPubKey
+        raw.extend_from_slice(&0u32.to_le_bytes()); // locktime
+        raw
+    }
+
+    /// Same logical transaction as `legacy_tx`, but SegWit-serialized
+    /// with a one-item witness on its single input.
+    fn segwit_tx() -> Vec<u8> {
+        let mut raw = Vec::new();
+        raw.extend_from_slice(&1i32.to_le_bytes()); // version
+        raw.push(0x00); // marker
+        raw.push(0x01); // flag
+        raw.push(1); // input count
+        raw.extend_from_slice(&[0u8; 32]);
+        raw.extend_from_slice(&0u32.to_le_bytes());
+        raw.push(0);
+        raw.extend_from_slice(&0xffff_ffffu32.to_le_bytes());
+        raw.push(1); // output count
+        raw.extend_from_slice(&0u64.to_le_bytes());
+        raw.push(0);
+        raw.push(1); // witness item count for the single input
+        raw.push(3); // item length
+        raw.extend_from_slice(&[0xaa, 0xbb, 0xcc]); // item bytes
+        raw.extend_from_slice(&0u32.to_le_bytes()); // locktime
+        raw
+    }
+
+    #[test]
+    fn segwit_and_legacy_txids_match_for_the_same_tx() {
+        let legacy = parse_btc_tx(&legacy_tx()).unwrap();
+        let segwit = parse_btc_tx(&segwit_tx()).unwrap();
+
+        assert_eq!(legacy.txid.as_slice(), segwit.txid.as_slice());
+        assert_eq!(segwit.inputs.len(), 1);
+        assert_eq!(segwit.outputs.len(), 1);
+    }
+
+    #[test]
+    fn zero_input_legacy_tx_is_not_mistaken_for_segwit() {
+        let mut raw = Vec::new();
+        raw.extend_from_slice(&1i32.to_le_bytes()); // version
+        raw.push(0); // input count = 0 (byte-identical to a SegWit marker)
+        raw.push(0); // output count = 0
+        raw.extend_from_slice(&0u32.to_le_bytes()); // locktime
+
+        let tx = parse_btc_tx(&raw).unwrap();
+        assert!(tx.inputs.is_empty());
+        assert!(tx.outputs.is_empty());
+    }
+
+    #[test]
+    fn zero_input_legacy_tx_with_nonzero_output_count_is_misparsed() {
+        // Known limitation (see parse_btc_tx doc comment): intended as a
+        // legacy tx with 0 inputs and 3 zero-value/empty-script outputs,
+        // but `0x00` (input count) followed by `0x03` (output count)
+        // byte-collides with a real SegWit marker+flag pair, so this is
+        // parsed as SegWit instead and the 3 outputs are lost. Locked in
+        // here so the ambiguity isn't silently assumed fixed.
+        let mut raw = Vec::new();
+        raw.extend_from_slice(&1i32.to_le_bytes()); // version
+        raw.push(0); // intended: input count = 0
+        raw.push(3); // intended: output count = 3
+        for _ in 0..3 {
+            raw.extend_from_slice(&0u64.to_le_bytes()); // value
+            raw.push(0); // empty scriptPubKey
+        }
+        raw.extend_from_slice(&0u32.to_le_bytes()); // locktime
+
+        let tx = parse_btc_tx(&raw).unwrap();
+        assert_eq!(tx.inputs.len(), 0);
+        assert_eq!(tx.outputs.len(), 0, "the 3 intended outputs were lost to the segwit misparse");
+    }
+
+    #[test]
+    fn rejects_truncated_witness() {
+        let mut raw = segwit_tx();
+        // Claim a witness item longer than the bytes actually present
+        // (raise the declared item length past the end of the tx).
+        let item_len_pos = raw.len() - 4 /* locktime */ - 3 /* item bytes */ - 1;
+        assert_eq!(raw[item_len_pos], 3);
+        raw[item_len_pos] = 200;
+
+        assert!(parse_btc_tx(&raw).is_err());
+    }
+}
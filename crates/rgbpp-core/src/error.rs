@@ -0,0 +1,45 @@
+use ckb_std::error::SysError;
+
+/// Error codes returned by the RGB++ contracts.
+///
+/// Variants are grouped by the subsystem that raises them: the first
+/// block mirrors `ckb_std::error::SysError` so syscall failures surface
+/// with a stable, non-overlapping exit code; the rest are specific to
+/// RGB++ script/witness validation.
+#[repr(i8)]
+#[derive(Debug, Eq, PartialEq)]
+pub enum Error {
+    IndexOutOfBound = 1,
+    ItemMissing,
+    LengthNotEnough,
+    Encoding,
+
+    BadRGBPPLock,
+    BadRGBPPUnlock,
+    BadBTCTimeLock,
+    BadConfig,
+    UtxoSealMismatch,
+    OutputCellWithUnknownLock,
+
+    BadBtcTx,
+    BadBtcCommitment,
+    UnknownCommitmentVersion,
+    CommitmentMismatch,
+
+    BtcLightClientNotFound,
+    BadBtcTxProof,
+    BtcTxNotFound,
+    InsufficientBtcConfirmations,
+}
+
+impl From<SysError> for Error {
+    fn from(err: SysError) -> Self {
+        match err {
+            SysError::IndexOutOfBound => Self::IndexOutOfBound,
+            SysError::ItemMissing => Self::ItemMissing,
+            SysError::LengthNotEnough(_) => Self::LengthNotEnough,
+            SysError::Encoding => Self::Encoding,
+            _ => unreachable!(),
+        }
+    }
+}
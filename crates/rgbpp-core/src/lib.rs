@@ -0,0 +1,39 @@
+//! rgbpp-core
+//!
+//! Shared, chain-agnostic logic for the RGB++ lock and BTC-time-lock
+//! scripts: Bitcoin tx parsing, light-client inclusion proofs, molecule
+//! schemas and the small set of helpers both contracts depend on.
+
+#![cfg_attr(not(test), no_std)]
+
+#[cfg(test)]
+extern crate alloc;
+#[cfg(not(test))]
+extern crate alloc;
+
+pub mod bitcoin;
+pub mod error;
+pub mod on_chain;
+pub mod rgbpp;
+pub mod schemas;
+pub mod utils;
+
+/// Return early with `$err` unless `$cond` holds.
+#[macro_export]
+macro_rules! ensure {
+    ($cond:expr, $err:expr) => {
+        if !($cond) {
+            return Err($err);
+        }
+    };
+}
+
+/// Return early with `$err` unless `$left == $right`.
+#[macro_export]
+macro_rules! ensure_eq {
+    ($left:expr, $right:expr, $err:expr) => {
+        if $left != $right {
+            return Err($err);
+        }
+    };
+}
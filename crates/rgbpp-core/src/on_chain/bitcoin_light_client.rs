@@ -0,0 +1,181 @@
+//! Lookup of the BTC light client cell tracked as a cell dep, and
+//! inclusion checks of a BTC transaction against the headers it stores.
+
+use alloc::vec::Vec;
+use ckb_std::ckb_constants::Source;
+use ckb_std::ckb_types::packed::Byte32;
+use ckb_std::ckb_types::prelude::*;
+use ckb_std::high_level::{load_cell_data, load_cell_type_hash, QueryIter};
+
+use crate::bitcoin::merkle::verify_partial_merkle_tree;
+use crate::ensure;
+use crate::ensure_eq;
+use crate::error::Error;
+use crate::schemas::rgbpp::RGBPPConfig;
+
+struct Header {
+    height: u32,
+    merkle_root: Byte32,
+}
+
+struct LightClient {
+    tip_height: u32,
+    headers: Vec<Header>,
+}
+
+impl LightClient {
+    /// Cell data layout: `tip_height:u32 | header_count:u32 | (height:u32 | merkle_root:[u8;32])*`.
+    fn from_cell_data(data: &[u8]) -> Result<Self, Error> {
+        let read_u32 = |data: &[u8], pos: usize| -> Result<u32, Error> {
+            let bytes: [u8; 4] = data
+                .get(pos..pos + 4)
+                .ok_or(Error::Encoding)?
+                .try_into()
+                .map_err(|_| Error::Encoding)?;
+            Ok(u32::from_le_bytes(bytes))
+        };
+
+        let tip_height = read_u32(data, 0)?;
+        let header_count = read_u32(data, 4)? as usize;
+        let mut headers = Vec::with_capacity(header_count);
+        let mut pos = 8;
+        for _ in 0..header_count {
+            let height = read_u32(data, pos)?;
+            let merkle_root = Byte32::from_slice(
+                data.get(pos + 4..pos + 36).ok_or(Error::Encoding)?,
+            )
+            .map_err(|_| Error::Encoding)?;
+            headers.push(Header {
+                height,
+                merkle_root,
+            });
+            pos += 36;
+        }
+        Ok(Self {
+            tip_height,
+            headers,
+        })
+    }
+
+    fn header_at(&self, height: u32) -> Option<&Header> {
+        self.headers.iter().find(|h| h.height == height)
+    }
+}
+
+fn load_light_client(btc_lc_type_hash: &Byte32) -> Result<LightClient, Error> {
+    let index = QueryIter::new(load_cell_type_hash, Source::CellDep)
+        .position(|type_hash| type_hash.as_ref() == Some(&btc_lc_type_hash.unpack()))
+        .ok_or(Error::BtcLightClientNotFound)?;
+    let data = load_cell_data(index, Source::CellDep)?;
+    LightClient::from_cell_data(&data)
+}
+
+fn read_u32(data: &[u8], pos: &mut usize) -> Result<u32, Error> {
+    let bytes: [u8; 4] = data
+        .get(*pos..*pos + 4)
+        .ok_or(Error::BadBtcTxProof)?
+        .try_into()
+        .map_err(|_| Error::BadBtcTxProof)?;
+    *pos += 4;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+/// Proof layout: `block_height:u32 | n_tx:u32 | hash_count:u32 | hashes[hash_count] | flag_len:u32 | flags[flag_len]`.
+struct BtcTxProof {
+    block_height: u32,
+    n_tx: u32,
+    hashes: Vec<Byte32>,
+    flags: Vec<u8>,
+}
+
+fn parse_proof(proof: &[u8]) -> Result<BtcTxProof, Error> {
+    let mut pos = 0;
+    let block_height = read_u32(proof, &mut pos)?;
+    let n_tx = read_u32(proof, &mut pos)?;
+    let hash_count = read_u32(proof, &mut pos)? as usize;
+    let mut hashes = Vec::with_capacity(hash_count);
+    for _ in 0..hash_count {
+        let hash_bytes = proof.get(pos..pos + 32).ok_or(Error::BadBtcTxProof)?;
+        hashes.push(Byte32::from_slice(hash_bytes).map_err(|_| Error::BadBtcTxProof)?);
+        pos += 32;
+    }
+    let flag_len = read_u32(proof, &mut pos)? as usize;
+    let flags = proof
+        .get(pos..pos + flag_len)
+        .ok_or(Error::BadBtcTxProof)?
+        .to_vec();
+    pos += flag_len;
+    ensure_eq!(pos, proof.len(), Error::BadBtcTxProof);
+
+    Ok(BtcTxProof {
+        block_height,
+        n_tx,
+        hashes,
+        flags,
+    })
+}
+
+/// Prove that `txid` is included, at some position, in a block tracked
+/// by the light client cell identified by `config.btc_lc_type_hash()`,
+/// buried under at least `config.min_confirmations()` blocks.
+///
+/// `proof` is a BIP37 partial merkle tree (see
+/// [`crate::bitcoin::merkle`]) alongside the height of the block it
+/// claims inclusion in; the derived root is checked against the header
+/// the light client stores for that height, and the single matched leaf
+/// must be `txid`.
+pub fn check_btc_tx_exists(config: &RGBPPConfig, txid: &Byte32, proof: &[u8]) -> Result<(), Error> {
+    let light_client = load_light_client(&config.btc_lc_type_hash())?;
+    let btc_tx_proof = parse_proof(proof)?;
+
+    let (root, matched) =
+        verify_partial_merkle_tree(btc_tx_proof.n_tx, &btc_tx_proof.hashes, &btc_tx_proof.flags)?;
+    ensure_eq!(matched.txid.as_slice(), txid.as_slice(), Error::BtcTxNotFound);
+
+    let header = light_client
+        .header_at(btc_tx_proof.block_height)
+        .ok_or(Error::BtcTxNotFound)?;
+    ensure_eq!(
+        root.as_slice(),
+        header.merkle_root.as_slice(),
+        Error::BtcTxNotFound
+    );
+
+    // Mirrors MIN_BTC_TIME_LOCK_AFTER's reorg-resistance guard on the
+    // BTC-time-lock path, but for the direct RGB++ unlock.
+    let confirmations = confirmations(light_client.tip_height, btc_tx_proof.block_height)?;
+    ensure!(
+        confirmations >= config.min_confirmations(),
+        Error::InsufficientBtcConfirmations
+    );
+
+    Ok(())
+}
+
+/// Number of confirmations a tx included at `block_height` has, relative
+/// to `tip_height`. Checked: `block_height` comes straight from the
+/// attacker-supplied proof, and nothing here guarantees it's `<=
+/// tip_height` independent of the light client's own invariants.
+fn confirmations(tip_height: u32, block_height: u32) -> Result<u32, Error> {
+    tip_height
+        .checked_sub(block_height)
+        .and_then(|depth| depth.checked_add(1))
+        .ok_or(Error::InsufficientBtcConfirmations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confirmations_counts_the_including_block_itself() {
+        assert_eq!(confirmations(100, 100).unwrap(), 1);
+        assert_eq!(confirmations(106, 100).unwrap(), 7);
+    }
+
+    #[test]
+    fn confirmations_rejects_a_block_height_above_tip() {
+        assert!(confirmations(100, 101).is_err());
+        assert!(confirmations(0, u32::MAX).is_err());
+    }
+}
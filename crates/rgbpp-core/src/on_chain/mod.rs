@@ -0,0 +1,2 @@
+pub mod bitcoin_light_client;
+pub mod utils;
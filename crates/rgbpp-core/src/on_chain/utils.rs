@@ -0,0 +1,27 @@
+use ckb_std::ckb_constants::Source;
+use ckb_std::ckb_types::packed::TransactionReader;
+use ckb_std::high_level::load_cell_data;
+
+use crate::error::Error;
+
+/// A molecule table that can be parsed with a plain `from_slice`.
+pub trait ConfigEntity: Sized {
+    fn from_slice(data: &[u8]) -> Result<Self, Error>;
+}
+
+impl ConfigEntity for crate::schemas::rgbpp::RGBPPConfig {
+    fn from_slice(data: &[u8]) -> Result<Self, Error> {
+        crate::schemas::rgbpp::RGBPPConfig::from_slice(data)
+    }
+}
+
+/// Load the contract config.
+///
+/// By convention the config cell is the transaction's first cell dep, so
+/// the lock can be reconfigured (e.g. to point at a new light client, or
+/// to retune `min_confirmations`) by upgrading that cell without a code
+/// upgrade.
+pub fn load_config<T: ConfigEntity>(_ckb_tx: &TransactionReader) -> Result<T, Error> {
+    let data = load_cell_data(0, Source::CellDep)?;
+    T::from_slice(&data)
+}
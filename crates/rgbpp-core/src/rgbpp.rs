@@ -0,0 +1,40 @@
+//! Seal-matching helpers shared by the RGB++ lock and the BTC-time-lock
+//! path it delegates expired/relocked outputs to.
+
+use ckb_std::ckb_types::packed::Script;
+use ckb_std::ckb_types::prelude::*;
+
+use crate::bitcoin::BTCTx;
+use crate::schemas::rgbpp::{BTCTimeLock, RGBPPConfig, RGBPPLock};
+
+/// Whether `lock` is the BTC-time-lock script this deployment recognizes.
+pub fn is_btc_time_lock(config: &RGBPPConfig, lock: &Script) -> bool {
+    lock.code_hash().as_slice() == config.btc_time_lock_code_hash().as_slice()
+}
+
+/// Whether `btc_tx` spends the UTXO an RGB++ cell's lock args seal.
+pub fn check_utxo_seal(lock_args: &RGBPPLock, btc_tx: &BTCTx) -> bool {
+    let expected = (lock_args.btc_txid(), lock_args.out_index().unpack());
+    btc_tx
+        .inputs
+        .iter()
+        .any(|txin| txin.previous_output == expected)
+}
+
+/// Whether `btc_tx` re-seals a BTC-time-locked UTXO, and does so no
+/// sooner than `min_after` blocks before it can be spent.
+///
+/// `min_after` mirrors `lock_args.after()`'s role in the light-client
+/// wait: the cell is only spendable once `btc_tx` is itself buried under
+/// at least `min_after` confirmations on top of `lock_args.btc_txid()`'s
+/// own inclusion, which the light client enforces when the output is
+/// later unlocked.
+pub fn check_btc_time_lock(lock_args: &BTCTimeLock, btc_tx: &BTCTx, min_after: u32) -> bool {
+    let expected_txid = lock_args.btc_txid();
+    let after = lock_args.after();
+    let found = btc_tx
+        .inputs
+        .iter()
+        .any(|txin| txin.previous_output.0.as_slice() == expected_txid.as_slice());
+    found && after >= min_after
+}
@@ -0,0 +1,53 @@
+//! Generated from `schemas/rgbpp.mol` (molecule table layout: a 4-byte
+//! `full_size` header, a field-offset table, then the field bodies).
+
+pub mod rgbpp;
+
+use alloc::vec::Vec;
+
+/// Build a molecule table from already-encoded field bytes.
+pub(crate) fn build_table(fields: &[&[u8]]) -> Vec<u8> {
+    let header_len = 4 + 4 * fields.len();
+    let mut offsets = Vec::with_capacity(fields.len());
+    let mut offset = header_len;
+    for field in fields {
+        offsets.push(offset as u32);
+        offset += field.len();
+    }
+    let full_size = offset as u32;
+
+    let mut out = Vec::with_capacity(offset);
+    out.extend_from_slice(&full_size.to_le_bytes());
+    for o in offsets {
+        out.extend_from_slice(&o.to_le_bytes());
+    }
+    for field in fields {
+        out.extend_from_slice(field);
+    }
+    out
+}
+
+/// Slice out field `index` of a `field_count`-field molecule table.
+pub(crate) fn read_table_field(
+    data: &[u8],
+    index: usize,
+    field_count: usize,
+) -> Result<&[u8], crate::error::Error> {
+    let header_len = 4 + 4 * field_count;
+    if data.len() < header_len {
+        return Err(crate::error::Error::Encoding);
+    }
+    let offset_at = |i: usize| -> Result<usize, crate::error::Error> {
+        let bytes: [u8; 4] = data[4 + 4 * i..8 + 4 * i]
+            .try_into()
+            .map_err(|_| crate::error::Error::Encoding)?;
+        Ok(u32::from_le_bytes(bytes) as usize)
+    };
+    let start = offset_at(index)?;
+    let end = if index + 1 < field_count {
+        offset_at(index + 1)?
+    } else {
+        data.len()
+    };
+    data.get(start..end).ok_or(crate::error::Error::Encoding)
+}
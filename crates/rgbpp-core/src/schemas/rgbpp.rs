@@ -0,0 +1,362 @@
+use alloc::vec::Vec;
+use ckb_std::ckb_types::packed::{Byte32, Script, Uint16, Uint32};
+use ckb_std::ckb_types::prelude::*;
+
+use super::{build_table, read_table_field};
+use crate::error::Error;
+
+/// `RGBPPLock { btc_txid: Byte32, out_index: Uint32 }`
+pub struct RGBPPLock {
+    btc_txid: Byte32,
+    out_index: u32,
+}
+
+impl RGBPPLock {
+    const FIELDS: usize = 2;
+
+    pub fn from_slice(data: &[u8]) -> Result<Self, Error> {
+        let btc_txid =
+            Byte32::from_slice(read_table_field(data, 0, Self::FIELDS)?).map_err(|_| Error::Encoding)?;
+        let out_index_bytes: [u8; 4] = read_table_field(data, 1, Self::FIELDS)?
+            .try_into()
+            .map_err(|_| Error::Encoding)?;
+        Ok(Self {
+            btc_txid,
+            out_index: u32::from_le_bytes(out_index_bytes),
+        })
+    }
+
+    pub fn btc_txid(&self) -> Byte32 {
+        self.btc_txid.clone()
+    }
+
+    pub fn out_index(&self) -> Uint32 {
+        self.out_index.pack()
+    }
+
+    pub fn as_builder(&self) -> RGBPPLockBuilder {
+        RGBPPLockBuilder {
+            btc_txid: self.btc_txid.clone(),
+            out_index: self.out_index,
+        }
+    }
+}
+
+pub struct RGBPPLockBuilder {
+    btc_txid: Byte32,
+    out_index: u32,
+}
+
+impl RGBPPLockBuilder {
+    pub fn btc_txid(mut self, btc_txid: Byte32) -> Self {
+        self.btc_txid = btc_txid;
+        self
+    }
+
+    pub fn build(self) -> RGBPPLock {
+        RGBPPLock {
+            btc_txid: self.btc_txid,
+            out_index: self.out_index,
+        }
+    }
+}
+
+impl RGBPPLock {
+    pub fn as_bytes(&self) -> Vec<u8> {
+        build_table(&[
+            self.btc_txid.as_slice(),
+            &self.out_index.to_le_bytes(),
+        ])
+    }
+}
+
+/// `RGBPPConfig { btc_lc_type_hash: Byte32, btc_time_lock_code_hash: Byte32, min_confirmations: Uint32 }`
+pub struct RGBPPConfig {
+    btc_lc_type_hash: Byte32,
+    btc_time_lock_code_hash: Byte32,
+    min_confirmations: u32,
+}
+
+impl RGBPPConfig {
+    const FIELDS: usize = 3;
+
+    pub fn from_slice(data: &[u8]) -> Result<Self, Error> {
+        let btc_lc_type_hash =
+            Byte32::from_slice(read_table_field(data, 0, Self::FIELDS)?).map_err(|_| Error::Encoding)?;
+        let btc_time_lock_code_hash =
+            Byte32::from_slice(read_table_field(data, 1, Self::FIELDS)?).map_err(|_| Error::Encoding)?;
+        let min_confirmations_bytes: [u8; 4] = read_table_field(data, 2, Self::FIELDS)?
+            .try_into()
+            .map_err(|_| Error::Encoding)?;
+        Ok(Self {
+            btc_lc_type_hash,
+            btc_time_lock_code_hash,
+            min_confirmations: u32::from_le_bytes(min_confirmations_bytes),
+        })
+    }
+
+    pub fn btc_lc_type_hash(&self) -> Byte32 {
+        self.btc_lc_type_hash.clone()
+    }
+
+    pub fn btc_time_lock_code_hash(&self) -> Byte32 {
+        self.btc_time_lock_code_hash.clone()
+    }
+
+    /// Minimum number of BTC blocks an unlock's `btc_tx` must be buried
+    /// under, relative to the light client's tracked tip, before the
+    /// RGB++ cells it seals may be unlocked.
+    pub fn min_confirmations(&self) -> u32 {
+        self.min_confirmations
+    }
+}
+
+/// `BTCTimeLock { lock_script: Script, after: Uint32, btc_txid: Byte32 }`
+pub struct BTCTimeLock {
+    lock_script: Script,
+    after: u32,
+    btc_txid: Byte32,
+}
+
+impl BTCTimeLock {
+    const FIELDS: usize = 3;
+
+    pub fn from_slice(data: &[u8]) -> Result<Self, Error> {
+        let lock_script =
+            Script::from_slice(read_table_field(data, 0, Self::FIELDS)?).map_err(|_| Error::Encoding)?;
+        let after_bytes: [u8; 4] = read_table_field(data, 1, Self::FIELDS)?
+            .try_into()
+            .map_err(|_| Error::Encoding)?;
+        let btc_txid =
+            Byte32::from_slice(read_table_field(data, 2, Self::FIELDS)?).map_err(|_| Error::Encoding)?;
+        Ok(Self {
+            lock_script,
+            after: u32::from_le_bytes(after_bytes),
+            btc_txid,
+        })
+    }
+
+    pub fn lock_script(&self) -> Script {
+        self.lock_script.clone()
+    }
+
+    pub fn after(&self) -> u32 {
+        self.after
+    }
+
+    pub fn btc_txid(&self) -> Byte32 {
+        self.btc_txid.clone()
+    }
+
+    pub fn as_builder(&self) -> BTCTimeLockBuilder {
+        BTCTimeLockBuilder {
+            lock_script: self.lock_script.clone(),
+            after: self.after,
+            btc_txid: self.btc_txid.clone(),
+        }
+    }
+}
+
+pub struct BTCTimeLockBuilder {
+    lock_script: Script,
+    after: u32,
+    btc_txid: Byte32,
+}
+
+impl BTCTimeLockBuilder {
+    pub fn btc_txid(mut self, btc_txid: Byte32) -> Self {
+        self.btc_txid = btc_txid;
+        self
+    }
+
+    pub fn build(self) -> BTCTimeLock {
+        BTCTimeLock {
+            lock_script: self.lock_script,
+            after: self.after,
+            btc_txid: self.btc_txid,
+        }
+    }
+}
+
+impl BTCTimeLock {
+    pub fn as_bytes(&self) -> Vec<u8> {
+        build_table(&[
+            self.lock_script.as_slice(),
+            &self.after.to_le_bytes(),
+            self.btc_txid.as_slice(),
+        ])
+    }
+}
+
+/// `ExtraData { input_len: Bytes, output_len: Bytes }`
+///
+/// `input_len`/`output_len` are raw byte strings rather than a fixed
+/// integer width: a version-0 unlock stores one byte per count, a
+/// version-1 unlock stores a little-endian `u16` per count. The caller
+/// decodes them according to `RGBPPUnlock.version()`.
+pub struct ExtraData {
+    input_len: Vec<u8>,
+    output_len: Vec<u8>,
+}
+
+impl ExtraData {
+    const FIELDS: usize = 2;
+
+    pub fn from_slice(data: &[u8]) -> Result<Self, Error> {
+        let input_len = read_table_field(data, 0, Self::FIELDS)?.to_vec();
+        let output_len = read_table_field(data, 1, Self::FIELDS)?.to_vec();
+        Ok(Self {
+            input_len,
+            output_len,
+        })
+    }
+
+    pub fn input_len_bytes(&self) -> &[u8] {
+        &self.input_len
+    }
+
+    pub fn output_len_bytes(&self) -> &[u8] {
+        &self.output_len
+    }
+}
+
+/// Decode the committed input/output counts out of `extra_data`,
+/// according to the commitment `version` that produced it.
+///
+/// Version 0 stores one byte per count; version 1 stores a
+/// little-endian `u16` per count, for batches version 0's byte range
+/// can't address. Malformed (too-short) fields return `Err` rather than
+/// panicking: both widths are read via `get`/`first`, never direct slice
+/// indexing, since `extra_data` comes straight from untrusted witness
+/// data.
+pub fn decode_commitment_lengths(version: u16, extra_data: &ExtraData) -> Result<(u32, u32), Error> {
+    match version {
+        0 => Ok((
+            extra_data
+                .input_len_bytes()
+                .first()
+                .copied()
+                .ok_or(Error::BadBtcCommitment)? as u32,
+            extra_data
+                .output_len_bytes()
+                .first()
+                .copied()
+                .ok_or(Error::BadBtcCommitment)? as u32,
+        )),
+        1 => {
+            let input_len = u16::from_le_bytes(
+                extra_data
+                    .input_len_bytes()
+                    .get(..2)
+                    .ok_or(Error::BadBtcCommitment)?
+                    .try_into()
+                    .map_err(|_| Error::BadBtcCommitment)?,
+            );
+            let output_len = u16::from_le_bytes(
+                extra_data
+                    .output_len_bytes()
+                    .get(..2)
+                    .ok_or(Error::BadBtcCommitment)?
+                    .try_into()
+                    .map_err(|_| Error::BadBtcCommitment)?,
+            );
+            Ok((input_len as u32, output_len as u32))
+        }
+        _ => Err(Error::UnknownCommitmentVersion),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extra_data_with(input_len: &[u8], output_len: &[u8]) -> ExtraData {
+        ExtraData {
+            input_len: input_len.to_vec(),
+            output_len: output_len.to_vec(),
+        }
+    }
+
+    #[test]
+    fn decodes_version_0_single_byte_lengths() {
+        let extra_data = extra_data_with(&[5], &[200]);
+        assert_eq!(decode_commitment_lengths(0, &extra_data).unwrap(), (5, 200));
+    }
+
+    #[test]
+    fn decodes_version_1_u16_lengths_above_255() {
+        let extra_data = extra_data_with(&300u16.to_le_bytes(), &1000u16.to_le_bytes());
+        assert_eq!(
+            decode_commitment_lengths(1, &extra_data).unwrap(),
+            (300, 1000)
+        );
+    }
+
+    #[test]
+    fn rejects_empty_lengths_instead_of_panicking() {
+        let extra_data = extra_data_with(&[], &[]);
+        assert!(decode_commitment_lengths(0, &extra_data).is_err());
+        assert!(decode_commitment_lengths(1, &extra_data).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_version() {
+        let extra_data = extra_data_with(&[1], &[1]);
+        assert!(decode_commitment_lengths(2, &extra_data).is_err());
+    }
+}
+
+/// `RGBPPUnlock { version: Uint16, btc_tx: Bytes, btc_tx_proof: Bytes, extra_data: ExtraData }`
+pub struct RGBPPUnlock {
+    version: u16,
+    btc_tx: Vec<u8>,
+    btc_tx_proof: Vec<u8>,
+    extra_data_bytes: Vec<u8>,
+}
+
+/// Owned view of a dynamic `Bytes` field, mirroring molecule's generated
+/// `Bytes` reader (`.raw_data()`).
+pub struct Bytes(Vec<u8>);
+
+impl Bytes {
+    pub fn raw_data(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+}
+
+impl RGBPPUnlock {
+    const FIELDS: usize = 4;
+
+    pub fn from_slice(data: &[u8]) -> Result<Self, Error> {
+        let version_bytes: [u8; 2] = read_table_field(data, 0, Self::FIELDS)?
+            .try_into()
+            .map_err(|_| Error::Encoding)?;
+        let btc_tx = read_table_field(data, 1, Self::FIELDS)?.to_vec();
+        let btc_tx_proof = read_table_field(data, 2, Self::FIELDS)?.to_vec();
+        let extra_data_bytes = read_table_field(data, 3, Self::FIELDS)?.to_vec();
+        // validate eagerly so a malformed extra_data is rejected up front
+        ExtraData::from_slice(&extra_data_bytes)?;
+        Ok(Self {
+            version: u16::from_le_bytes(version_bytes),
+            btc_tx,
+            btc_tx_proof,
+            extra_data_bytes,
+        })
+    }
+
+    pub fn version(&self) -> Uint16 {
+        self.version.pack()
+    }
+
+    pub fn btc_tx(&self) -> Bytes {
+        Bytes(self.btc_tx.clone())
+    }
+
+    pub fn btc_tx_proof(&self) -> Bytes {
+        Bytes(self.btc_tx_proof.clone())
+    }
+
+    pub fn extra_data(&self) -> ExtraData {
+        ExtraData::from_slice(&self.extra_data_bytes).expect("validated in from_slice")
+    }
+}
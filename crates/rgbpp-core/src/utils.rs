@@ -0,0 +1,10 @@
+use ckb_std::ckb_types::packed::Script;
+
+/// Compare two scripts by code (code_hash + hash_type), ignoring args.
+///
+/// Used to recognize "another cell locked by the same script family"
+/// (e.g. another RGB++ cell, or a BTC-time-lock cell) regardless of the
+/// seal/args payload carried in each cell's lock args.
+pub fn is_script_code_equal(a: &Script, b: &Script) -> bool {
+    a.code_hash().as_slice() == b.code_hash().as_slice() && a.hash_type() == b.hash_type()
+}